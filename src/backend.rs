@@ -0,0 +1,107 @@
+//! Backend abstraction, to decouple Cursive from any given terminal library.
+//!
+//! Everything Cursive needs from the terminal - initializing it, reading
+//! its size, drawing text, reading input and cleaning up afterwards - goes
+//! through the `Backend` trait. The only implementation provided here
+//! wraps ncurses, but other crates can plug in termion, crossterm, or a
+//! headless backend that just records what was drawn, for tests.
+use ncurses;
+
+use color;
+use vec::Vec2;
+
+/// Trait defining the interface expected from a backend.
+pub trait Backend {
+    /// Initializes the backend, preparing it to draw things.
+    fn init() -> Self where Self: Sized;
+
+    /// Stops the backend, restoring the terminal to its original state.
+    fn finish(&mut self);
+
+    /// Clears the screen.
+    fn clear(&self);
+
+    /// Flushes anything that was drawn since the last refresh.
+    fn refresh(&self);
+
+    /// Returns the size of the screen, in characters.
+    fn screen_size(&self) -> Vec2;
+
+    /// Prints `text` at the given position.
+    fn print_at(&self, pos: Vec2, text: &str);
+
+    /// Selects the given color pair for the next draw calls.
+    fn set_color_pair(&self, pair: i16);
+
+    /// Sets how long `poll_event` may block waiting for input.
+    ///
+    /// Call with fps=0 to block indefinitely (the default).
+    fn set_refresh_rate(&self, fps: u32);
+
+    /// Blocks (up to the configured refresh rate) until the next input
+    /// event, and returns it.
+    fn poll_event(&self) -> i32;
+}
+
+/// Backend implementation based on ncurses, used before the backend split.
+pub struct NcursesBackend;
+
+impl Backend for NcursesBackend {
+    fn init() -> Self {
+        ncurses::setlocale(ncurses::LcCategory::all, "");
+        ncurses::initscr();
+        ncurses::keypad(ncurses::stdscr, true);
+        ncurses::noecho();
+        ncurses::cbreak();
+        ncurses::start_color();
+        ncurses::curs_set(ncurses::CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+
+        color::load_legacy();
+        // color::load_default();
+        // color::load_theme("assets/style.toml").ok().unwrap();
+
+        ncurses::wbkgd(ncurses::stdscr, ncurses::COLOR_PAIR(color::BACKGROUND));
+
+        NcursesBackend
+    }
+
+    fn finish(&mut self) {
+        ncurses::endwin();
+    }
+
+    fn clear(&self) {
+        ncurses::clear();
+    }
+
+    fn refresh(&self) {
+        ncurses::refresh();
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        let mut x: i32 = 0;
+        let mut y: i32 = 0;
+        ncurses::getmaxyx(ncurses::stdscr, &mut y, &mut x);
+
+        Vec2::new(x as usize, y as usize)
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        ncurses::mvprintw(pos.y as i32, pos.x as i32, text);
+    }
+
+    fn set_color_pair(&self, pair: i16) {
+        ncurses::attron(ncurses::COLOR_PAIR(pair));
+    }
+
+    fn set_refresh_rate(&self, fps: u32) {
+        if fps == 0 {
+            ncurses::timeout(-1);
+        } else {
+            ncurses::timeout(1000 / fps as i32);
+        }
+    }
+
+    fn poll_event(&self) -> i32 {
+        ncurses::getch()
+    }
+}
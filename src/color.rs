@@ -0,0 +1,20 @@
+//! Color management.
+//!
+//! Colors are identified by their ncurses color pair id. This module only
+//! defines the legacy, hard-coded palette; a future `load_theme` could
+//! read pairs from a toml file instead.
+use ncurses;
+
+/// Color pair used for the main background.
+pub const BACKGROUND: i16 = 1;
+/// Color pair used to draw shadows under views.
+pub const SHADOW: i16 = 2;
+/// Color pair used for the content of views (as opposed to the background).
+pub const PRIMARY: i16 = 3;
+
+/// Initializes the legacy set of color pairs.
+pub fn load_legacy() {
+    ncurses::init_pair(BACKGROUND, ncurses::COLOR_WHITE, ncurses::COLOR_BLUE);
+    ncurses::init_pair(SHADOW, ncurses::COLOR_BLACK, ncurses::COLOR_BLACK);
+    ncurses::init_pair(PRIMARY, ncurses::COLOR_BLACK, ncurses::COLOR_WHITE);
+}
@@ -0,0 +1,65 @@
+//! A thin layer of global components, drawn above the active screen.
+//!
+//! Components pushed onto the `Compositor` float above every screen, and
+//! the top-most one gets first crack at input, before it reaches the
+//! active screen's `on_key_event`; components below the top-most one are
+//! drawn but are not given events. The active screen has no such
+//! mechanism of its own (the global
+//! callback map only fires *after* the active view ignores a key). This
+//! gives a clean home for things like a persistent status bar, a modal
+//! command palette, or transient notifications.
+use event::{Event,EventResult};
+use printer::Printer;
+
+/// A self-contained piece of UI, drawn and fed events ahead of screens.
+pub trait Component {
+    /// Draws this component over whatever is below it.
+    fn render(&self, printer: &Printer);
+
+    /// Handles an event, before the active screen gets a chance to.
+    ///
+    /// Returning `EventResult::Ignored` lets the event propagate down to
+    /// the active screen.
+    fn handle_event(&mut self, event: Event) -> EventResult;
+}
+
+/// Ordered stack of global `Component`s, drawn over the active screen.
+pub struct Compositor {
+    layers: Vec<Box<Component>>,
+}
+
+impl Compositor {
+    /// Creates a new, empty compositor.
+    pub fn new() -> Self {
+        Compositor { layers: Vec::new() }
+    }
+
+    /// Pushes a new component on top of the stack.
+    pub fn push(&mut self, component: Box<Component>) {
+        self.layers.push(component);
+    }
+
+    /// Pops the top-most component off the stack, if any.
+    pub fn pop(&mut self) -> Option<Box<Component>> {
+        self.layers.pop()
+    }
+
+    /// Draws every component, bottom to top, over the given printer.
+    pub fn draw(&self, printer: &Printer) {
+        for layer in &self.layers {
+            layer.render(printer);
+        }
+    }
+
+    /// Gives the top-most component first crack at the given event.
+    ///
+    /// Returns `EventResult::Ignored` if there is no component, or the
+    /// top-most one ignored the event; the caller should then forward it
+    /// further down (typically to the active screen).
+    pub fn handle_event(&mut self, event: Event) -> EventResult {
+        match self.layers.last_mut() {
+            None => EventResult::Ignored,
+            Some(c) => c.handle_event(event),
+        }
+    }
+}
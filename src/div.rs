@@ -0,0 +1,15 @@
+//! Helper to divide a length between several children.
+
+/// Splits `len` into `n` parts, as evenly as possible.
+///
+/// The remainder (if any) is distributed among the first few parts.
+pub fn split(len: usize, n: usize) -> Vec<usize> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let base = len / n;
+    let extra = len % n;
+
+    (0..n).map(|i| if i < extra { base + 1 } else { base }).collect()
+}
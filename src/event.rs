@@ -0,0 +1,39 @@
+//! Defines events and their result.
+use Cursive;
+
+/// A callback that can be triggered by an event.
+pub type Callback = Box<Fn(&mut Cursive)>;
+
+/// A callback queued from outside the event loop, through `cb_sink`.
+///
+/// Must be `Send`: it is handed to a `mpsc::Sender` so another thread can
+/// push it across.
+pub type AnyCb = Box<FnOnce(&mut Cursive) + Send>;
+
+/// An event that can be given to a view, to be handled or ignored.
+#[derive(Clone, Copy)]
+pub enum Event {
+    /// A key was pressed (as reported by the backend).
+    Key(i32),
+    /// No input arrived before the refresh rate elapsed.
+    ///
+    /// This is the hook animated views (progress bars, spinners, ...)
+    /// should use to advance their state.
+    Refresh,
+}
+
+/// Represents the outcome of an event, once given to a view.
+pub enum EventResult {
+    /// The view ignored the event. Something above should deal with it.
+    Ignored,
+    /// The view consumed the event, and optionally asks for a callback
+    /// to be run afterwards.
+    Consumed(Option<Callback>),
+}
+
+impl EventResult {
+    /// Convenient method to create `Consumed(Some(f))`.
+    pub fn with_cb<F: 'static + Fn(&mut Cursive)>(f: F) -> Self {
+        EventResult::Consumed(Some(Box::new(f)))
+    }
+}
@@ -23,6 +23,8 @@
 extern crate ncurses;
 extern crate toml;
 
+pub mod backend;
+pub mod compositor;
 pub mod event;
 pub mod view;
 pub mod printer;
@@ -34,20 +36,23 @@ mod div;
 use std::any::Any;
 use std::rc::Rc;
 use std::collections::HashMap;
+use std::sync::mpsc;
 
+use backend::{Backend,NcursesBackend};
+use compositor::{Component,Compositor};
 use vec::Vec2;
 use view::View;
 use printer::Printer;
-use view::{StackView,Selector};
+use view::{PromptView,StackView,Selector};
 
-use event::{EventResult,Callback};
+use event::{AnyCb,Event,EventResult,Callback};
 
 /// Identifies a screen in the cursive ROOT.
 pub type ScreenId = usize;
 
 /// Central part of the cursive library.
 ///
-/// It initializes ncurses on creation and cleans up on drop.
+/// It initializes a backend on creation and cleans it up on drop.
 /// To use it, you should populate it with views, layouts and callbacks,
 /// then start the event loop with run().
 ///
@@ -60,29 +65,38 @@ pub struct Cursive {
     running: bool,
 
     global_callbacks: HashMap<i32, Rc<Callback>>,
+
+    backend: Box<Backend>,
+
+    cb_sink: mpsc::Sender<AnyCb>,
+    cb_source: mpsc::Receiver<AnyCb>,
+
+    compositor: Compositor,
 }
 
 impl Cursive {
-    /// Creates a new Cursive root, and initialize ncurses.
+    /// Creates a new Cursive root, and initializes the backend.
     pub fn new() -> Self {
-        ncurses::setlocale(ncurses::LcCategory::all, "");
-        ncurses::initscr();
-        ncurses::keypad(ncurses::stdscr, true);
-        ncurses::noecho();
-        ncurses::cbreak();
-        ncurses::start_color();
-        ncurses::curs_set(ncurses::CURSOR_VISIBILITY::CURSOR_INVISIBLE);
-        color::load_legacy();
-        // color::load_default();
-        // color::load_theme("assets/style.toml").ok().unwrap();
-
-        ncurses::wbkgd(ncurses::stdscr, ncurses::COLOR_PAIR(color::BACKGROUND));
+        Cursive::from_backend(Box::new(NcursesBackend::init()))
+    }
+
+    /// Creates a new Cursive root using the given backend.
+    ///
+    /// This lets applications plug in an alternative to ncurses (termion,
+    /// crossterm, a headless backend for tests, ...) instead of the
+    /// default one used by `new()`.
+    pub fn from_backend(backend: Box<Backend>) -> Self {
+        let (tx, rx) = mpsc::channel();
 
         let mut res = Cursive {
             screens: Vec::new(),
             active_screen: 0,
             running: true,
             global_callbacks: HashMap::new(),
+            backend: backend,
+            cb_sink: tx,
+            cb_source: rx,
+            compositor: Compositor::new(),
         };
 
         res.screens.push(StackView::new());
@@ -90,15 +104,27 @@ impl Cursive {
         res
     }
 
+    /// Returns a sink for asynchronously sending callbacks to be run
+    /// during the event loop.
+    ///
+    /// This is the recommended way to update the UI from another thread:
+    /// clone the returned sender, move it there, and call `send` with a
+    /// closure whenever it needs to touch the `Cursive` root.
+    ///
+    /// Queued callbacks are only picked up between two iterations of the
+    /// event loop, right before it blocks again waiting for input - so
+    /// they run promptly only while `set_fps` is non-zero (the default,
+    /// `fps=0`, blocks indefinitely on input and leaves callbacks waiting
+    /// until the next key press).
+    pub fn cb_sink(&self) -> mpsc::Sender<AnyCb> {
+        self.cb_sink.clone()
+    }
+
     /// Regularly redraws everything, even when no input is given. Between 0 and 1000.
     ///
     /// Call with fps=0 to disable (default value).
     pub fn set_fps(&self, fps: u32) {
-        if fps == 0 {
-            ncurses::timeout(-1);
-        } else {
-            ncurses::timeout(1000 / fps as i32);
-        }
+        self.backend.set_refresh_rate(fps);
     }
 
     /// Returns a mutable reference to the currently active screen.
@@ -156,6 +182,32 @@ impl Cursive {
         self.screen_mut().add_layer(view);
     }
 
+    /// Pushes a component on top of the compositor.
+    ///
+    /// Components float above every screen and are drawn over it; the
+    /// top-most one is given first crack at events before anything else
+    /// (components below it are not given events) - a good place for a
+    /// persistent status bar, a modal command palette, or a transient
+    /// notification.
+    pub fn push_component(&mut self, component: Box<Component>) {
+        self.compositor.push(component);
+    }
+
+    /// Pops the top-most component off the compositor, if any.
+    pub fn pop_component(&mut self) -> Option<Box<Component>> {
+        self.compositor.pop()
+    }
+
+    /// Shows a command prompt, floating above every screen.
+    ///
+    /// This is the motivating use case for the compositor: the prompt
+    /// is pushed as a `Component`, gets first crack at every key press
+    /// (so the active screen never sees them while it's up), and pops
+    /// itself back off once the user presses Enter.
+    pub fn show_prompt(&mut self, prompt: PromptView) {
+        self.push_component(Box::new(prompt));
+    }
+
     // Handles a key event when it was ignored by the current view
     fn on_key_event(&mut self, ch: i32) {
         let cb = match self.global_callbacks.get(&ch) {
@@ -168,14 +220,7 @@ impl Cursive {
 
     /// Returns the size of the screen, in characters.
     pub fn screen_size(&self) -> Vec2 {
-        let mut x: i32 = 0;
-        let mut y: i32 = 0;
-        ncurses::getmaxyx(ncurses::stdscr, &mut y, &mut x);
-
-        Vec2 {
-            x: x as usize,
-            y: y as usize,
-        }
+        self.backend.screen_size()
     }
 
     fn layout(&mut self) {
@@ -183,13 +228,33 @@ impl Cursive {
         self.screen_mut().layout(size);
     }
 
-    fn draw(&mut self) {
-        let printer = Printer {
-            offset: Vec2::new(0,0),
-            size: self.screen_size(),
-        };
-        self.screen_mut().draw(&printer, true);
-        ncurses::refresh();
+    fn draw(&self) {
+        // Only reads `self.screens`/`self.compositor`/`self.backend`, so this can take `&self`
+        // and avoid fighting the borrow checker over `self.backend` being
+        // borrowed by `printer` while a `&mut self` method is also needed.
+        let printer = Printer::new(self.screen_size(), &*self.backend);
+        self.screens[self.active_screen].draw(&printer, true);
+        self.compositor.draw(&printer);
+        self.backend.refresh();
+    }
+
+    // Runs every callback sent through `cb_sink` since the last time we checked.
+    fn process_callbacks(&mut self) {
+        while let Ok(cb) = self.cb_source.try_recv() {
+            cb(self);
+        }
+    }
+
+    // Polls the backend for the next event.
+    //
+    // The backend's refresh rate (set through `set_fps`) controls how long
+    // this blocks: if no key arrives before it elapses, a `Refresh` event
+    // is returned instead, giving animated views a chance to advance.
+    fn poll_event(&self) -> Event {
+        match self.backend.poll_event() {
+            -1 => Event::Refresh,
+            ch => Event::Key(ch),
+        }
     }
 
     /// Runs the event loop.
@@ -202,20 +267,37 @@ impl Cursive {
             // Do we need to redraw everytime?
             // Probably, actually.
             // TODO: Do we actually need to clear everytime?
-            ncurses::clear();
+            self.backend.clear();
             // TODO: Do we need to re-layout everytime?
             self.layout();
             // TODO: Do we need to redraw every view every time?
             // (Is this getting repetitive? :p)
             self.draw();
 
-            // Blocks until the user press a key.
-            // TODO: Add a timeout? Animations?
-            let ch = ncurses::getch();
-
-            // If the event was ignored, it is our turn to play with it.
-            match self.screen_mut().on_key_event(ch) {
-                EventResult::Ignored => self.on_key_event(ch),
+            // Run any callback queued from another thread before the next event.
+            self.process_callbacks();
+
+            // Blocks until the user presses a key, or the refresh rate elapses.
+            let event = self.poll_event();
+
+            // The compositor gets first crack at it; screens only see what
+            // it ignores.
+            match self.compositor.handle_event(event) {
+                EventResult::Ignored => match event {
+                    Event::Key(ch) => {
+                        // If the event was ignored, it is our turn to play with it.
+                        match self.screen_mut().on_key_event(ch) {
+                            EventResult::Ignored => self.on_key_event(ch),
+                            EventResult::Consumed(None) => (),
+                            EventResult::Consumed(Some(cb)) => cb(self),
+                        }
+                    }
+                    Event::Refresh => {
+                        if let EventResult::Consumed(Some(cb)) = self.screen_mut().on_event(Event::Refresh) {
+                            cb(self);
+                        }
+                    }
+                },
                 EventResult::Consumed(None) => (),
                 EventResult::Consumed(Some(cb)) => cb(self),
             }
@@ -230,7 +312,7 @@ impl Cursive {
 
 impl Drop for Cursive {
     fn drop(&mut self) {
-        ncurses::endwin();
+        self.backend.finish();
     }
 }
 
@@ -0,0 +1,51 @@
+//! Tools to write text on screen.
+use backend::Backend;
+use color;
+use vec::Vec2;
+
+/// Convenient interface to draw on a subset of the screen.
+///
+/// All draw calls are relative to the printer's offset, and routed through
+/// whatever backend is currently in use.
+pub struct Printer<'a> {
+    /// Offset into the screen for this printer.
+    pub offset: Vec2,
+    /// Size of the area we are allowed to draw on.
+    pub size: Vec2,
+    /// Backend used to actually draw things.
+    backend: &'a Backend,
+}
+
+impl<'a> Printer<'a> {
+    /// Creates a new printer on the whole screen, using the given backend.
+    pub fn new(size: Vec2, backend: &'a Backend) -> Self {
+        Printer {
+            offset: Vec2::zero(),
+            size: size,
+            backend: backend,
+        }
+    }
+
+    /// Prints some text at the given position relative to the top-left
+    /// corner of the printer.
+    pub fn print(&self, pos: Vec2, text: &str) {
+        self.backend.print_at(self.offset + pos, text);
+    }
+
+    /// Returns a sub-printer, offset by the given amount.
+    pub fn sub_printer(&self, offset: Vec2, size: Vec2) -> Printer<'a> {
+        Printer {
+            offset: self.offset + offset,
+            size: size,
+            backend: self.backend,
+        }
+    }
+
+    /// Runs `f` with the given color pair selected, then switches back to
+    /// `color::PRIMARY`.
+    pub fn with_color(&self, pair: i16, f: &Fn(&Printer)) {
+        self.backend.set_color_pair(pair);
+        f(self);
+        self.backend.set_color_pair(color::PRIMARY);
+    }
+}
@@ -0,0 +1,29 @@
+//! Basic 2D size/position vector.
+use std::ops::Add;
+
+/// Simple 2D size, in characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vec2 {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Vec2 {
+    /// Creates a new Vec2 from the given coordinates.
+    pub fn new(x: usize, y: usize) -> Self {
+        Vec2 { x: x, y: y }
+    }
+
+    /// Returns a Vec2 with the zero value.
+    pub fn zero() -> Self {
+        Vec2::new(0, 0)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Self) -> Self {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
@@ -0,0 +1,412 @@
+//! Defines the `View` trait, and provides some common implementations.
+use std::any::Any;
+use std::rc::Rc;
+
+use ncurses;
+
+use compositor::Component;
+use event::{Event,EventResult};
+use printer::Printer;
+use vec::Vec2;
+use Cursive;
+
+/// Selects a view, to be used with `Cursive::find`.
+pub enum Selector<'a> {
+    /// Selects a view from its ID.
+    Id(&'a str),
+}
+
+/// Main trait defining a view behaviour.
+pub trait View {
+    /// Draws the view with the given printer (includes bounds) and focus.
+    fn draw(&self, printer: &Printer, focused: bool);
+
+    /// Called when a key was pressed, and this view is the one catching it.
+    ///
+    /// Default implementation just ignores it.
+    fn on_key_event(&mut self, _: i32) -> EventResult {
+        EventResult::Ignored
+    }
+
+    /// Called on events other than key presses, such as `Event::Refresh`.
+    ///
+    /// Animated views (progress bars, spinners, ...) can use this to
+    /// advance their state on every tick. Ignored by default.
+    fn on_event(&mut self, _: Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    /// Called once the size for this view has been decided, so it can
+    /// propagate the information to its children.
+    fn layout(&mut self, _: Vec2) {}
+
+    /// Returns the minimum size this view requires, under the given
+    /// constraint.
+    fn get_min_size(&self, constraint: Vec2) -> Vec2 {
+        constraint
+    }
+
+    /// Finds the view pointed to by the given selector.
+    ///
+    /// Returns None if the selector doesn't find a match.
+    fn find(&mut self, _: &Selector) -> Option<&mut Any> {
+        None
+    }
+}
+
+/// Simple view showing a fixed text.
+pub struct TextView {
+    content: String,
+}
+
+impl TextView {
+    /// Creates a new TextView with the given content.
+    pub fn new<S: Into<String>>(content: S) -> Self {
+        TextView { content: content.into() }
+    }
+}
+
+impl View for TextView {
+    fn draw(&self, printer: &Printer, _: bool) {
+        printer.print(Vec2::zero(), &self.content);
+    }
+}
+
+/// A simple stack of views, only the top one being active.
+///
+/// All layers are drawn on top of each other; only the top-most layer
+/// receives events.
+pub struct StackView {
+    layers: Vec<Box<View>>,
+}
+
+impl StackView {
+    /// Creates a new, empty StackView.
+    pub fn new() -> Self {
+        StackView { layers: Vec::new() }
+    }
+
+    /// Adds a new layer on top of the stack.
+    pub fn add_layer<T: 'static + View>(&mut self, view: T) {
+        self.layers.push(Box::new(view));
+    }
+
+    /// Removes the top-most layer, and returns it.
+    pub fn pop_layer(&mut self) -> Option<Box<View>> {
+        self.layers.pop()
+    }
+
+    /// Returns the number of layers in this stack.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+}
+
+impl View for StackView {
+    fn draw(&self, printer: &Printer, focused: bool) {
+        for (i, layer) in self.layers.iter().enumerate() {
+            let top = i + 1 == self.layers.len();
+            layer.draw(printer, focused && top);
+        }
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        for layer in self.layers.iter_mut() {
+            layer.layout(size);
+        }
+    }
+
+    fn on_key_event(&mut self, ch: i32) -> EventResult {
+        match self.layers.last_mut() {
+            None => EventResult::Ignored,
+            Some(v) => v.on_key_event(ch),
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match self.layers.last_mut() {
+            None => EventResult::Ignored,
+            Some(v) => v.on_event(event),
+        }
+    }
+
+    fn find(&mut self, selector: &Selector) -> Option<&mut Any> {
+        match self.layers.last_mut() {
+            None => None,
+            Some(v) => v.find(selector),
+        }
+    }
+}
+
+/// A single-line command prompt, meant to float above every screen
+/// (e.g. bound to a key like `:` through a global callback, and shown
+/// with `Cursive::show_prompt`).
+///
+/// Supports left/right cursor movement, backspace, an in-memory history
+/// navigable with up/down, and tab-completion through a user-supplied
+/// callback. On Enter, it calls back with the entered text and dismisses
+/// itself by popping itself off the compositor.
+pub struct PromptView {
+    content: String,
+    cursor: usize,
+
+    history: Vec<String>,
+    history_pos: Option<usize>,
+
+    completer: Option<Box<Fn(&str) -> Vec<String>>>,
+    completions: Vec<String>,
+    completion_pos: usize,
+
+    on_submit: Rc<Box<Fn(&mut Cursive, &str)>>,
+}
+
+impl PromptView {
+    /// Creates a new, empty prompt.
+    ///
+    /// `on_submit` is called with the entered text when the user presses
+    /// Enter, right before the prompt dismisses itself.
+    pub fn new<F>(on_submit: F) -> Self
+        where F: 'static + Fn(&mut Cursive, &str)
+    {
+        PromptView {
+            content: String::new(),
+            cursor: 0,
+            history: Vec::new(),
+            history_pos: None,
+            completer: None,
+            completions: Vec::new(),
+            completion_pos: 0,
+            on_submit: Rc::new(Box::new(on_submit)),
+        }
+    }
+
+    /// Sets the callback used to compute completion candidates for the
+    /// text entered so far. Triggered on Tab, and cycles through the
+    /// returned candidates on repeated presses.
+    pub fn completer<F>(mut self, completer: F) -> Self
+        where F: 'static + Fn(&str) -> Vec<String>
+    {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    fn reset_completions(&mut self) {
+        self.completions.clear();
+        self.completion_pos = 0;
+    }
+
+    // `cursor` counts chars, not bytes (multi-byte chars would otherwise
+    // land `cursor` off a UTF-8 boundary); convert to a byte offset before
+    // touching `content` directly.
+    fn cursor_byte_pos(&self) -> usize {
+        self.content
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| self.content.len())
+    }
+
+    fn char_len(&self) -> usize {
+        self.content.chars().count()
+    }
+
+    fn complete(&mut self) {
+        if self.completions.is_empty() {
+            let candidates = match self.completer {
+                None => return,
+                Some(ref f) => f(&self.content),
+            };
+            self.completions = candidates;
+            self.completion_pos = 0;
+        } else {
+            self.completion_pos = (self.completion_pos + 1) % self.completions.len();
+        }
+
+        if let Some(candidate) = self.completions.get(self.completion_pos).cloned() {
+            self.content = candidate;
+            self.cursor = self.char_len();
+        }
+    }
+
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let pos = match self.history_pos {
+            None => self.history.len() - 1,
+            Some(0) => 0,
+            Some(p) => p - 1,
+        };
+        self.history_pos = Some(pos);
+        self.content = self.history[pos].clone();
+        self.cursor = self.char_len();
+    }
+
+    fn history_next(&mut self) {
+        match self.history_pos {
+            None => (),
+            Some(p) if p + 1 < self.history.len() => {
+                self.history_pos = Some(p + 1);
+                self.content = self.history[p + 1].clone();
+                self.cursor = self.char_len();
+            }
+            Some(_) => {
+                self.history_pos = None;
+                self.content.clear();
+                self.cursor = 0;
+            }
+        }
+    }
+
+    fn submit(&mut self) -> EventResult {
+        let input = self.content.clone();
+        self.history.push(input.clone());
+        self.history_pos = None;
+        self.content.clear();
+        self.cursor = 0;
+        self.reset_completions();
+
+        let on_submit = self.on_submit.clone();
+
+        EventResult::with_cb(move |s| {
+            on_submit(s, &input);
+            s.pop_component();
+        })
+    }
+}
+
+impl View for PromptView {
+    fn draw(&self, printer: &Printer, _: bool) {
+        printer.print(Vec2::zero(), &self.content);
+    }
+
+    fn on_key_event(&mut self, ch: i32) -> EventResult {
+        match ch {
+            ncurses::KEY_LEFT => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                }
+                EventResult::Consumed(None)
+            }
+            ncurses::KEY_RIGHT => {
+                if self.cursor < self.char_len() {
+                    self.cursor += 1;
+                }
+                EventResult::Consumed(None)
+            }
+            ncurses::KEY_BACKSPACE | 127 => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    let byte_pos = self.cursor_byte_pos();
+                    self.content.remove(byte_pos);
+                    self.reset_completions();
+                }
+                EventResult::Consumed(None)
+            }
+            ncurses::KEY_UP => {
+                self.history_prev();
+                EventResult::Consumed(None)
+            }
+            ncurses::KEY_DOWN => {
+                self.history_next();
+                EventResult::Consumed(None)
+            }
+            9 => {
+                // Tab
+                self.complete();
+                EventResult::Consumed(None)
+            }
+            10 | 13 => self.submit(),
+            ch if ch >= 0 && ch < 256 => {
+                let byte_pos = self.cursor_byte_pos();
+                self.content.insert(byte_pos, ch as u8 as char);
+                self.cursor += 1;
+                self.reset_completions();
+                EventResult::Consumed(None)
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+impl Component for PromptView {
+    fn render(&self, printer: &Printer) {
+        // Float on the last row, like a real command line, rather than
+        // drawing over whatever is in the screen's top-left corner.
+        let y = printer.size.y.saturating_sub(1);
+        let bar = printer.sub_printer(Vec2::new(0, y), Vec2::new(printer.size.x, 1));
+        self.draw(&bar, true);
+    }
+
+    fn handle_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(ch) => self.on_key_event(ch),
+            Event::Refresh => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_navigates_without_wrapping() {
+        let mut prompt = PromptView::new(|_, _| ());
+
+        for ch in "a".chars() {
+            prompt.on_key_event(ch as i32);
+        }
+        prompt.on_key_event(10); // Enter: submits "a"
+
+        for ch in "b".chars() {
+            prompt.on_key_event(ch as i32);
+        }
+        prompt.on_key_event(10); // Enter: submits "b"
+
+        // Pressing up walks back through history, and stops at the oldest
+        // entry instead of wrapping or panicking.
+        prompt.on_key_event(ncurses::KEY_UP);
+        assert_eq!(prompt.content, "b");
+        prompt.on_key_event(ncurses::KEY_UP);
+        assert_eq!(prompt.content, "a");
+        prompt.on_key_event(ncurses::KEY_UP);
+        assert_eq!(prompt.content, "a");
+
+        // Pressing down walks back down, clearing the line once past the
+        // most recent entry.
+        prompt.on_key_event(ncurses::KEY_DOWN);
+        assert_eq!(prompt.content, "b");
+        prompt.on_key_event(ncurses::KEY_DOWN);
+        assert_eq!(prompt.content, "");
+    }
+
+    #[test]
+    fn completion_cycles_through_candidates() {
+        let mut prompt = PromptView::new(|_, _| ())
+            .completer(|_| vec!["foo".to_string(), "bar".to_string()]);
+
+        prompt.on_key_event(9); // Tab
+        assert_eq!(prompt.content, "foo");
+        prompt.on_key_event(9);
+        assert_eq!(prompt.content, "bar");
+        prompt.on_key_event(9);
+        assert_eq!(prompt.content, "foo");
+    }
+
+    #[test]
+    fn multi_byte_input_does_not_panic() {
+        let mut prompt = PromptView::new(|_, _| ());
+
+        // Two consecutive 2-byte-in-UTF-8 chars used to desync `cursor`
+        // (a char count) from the string's byte offsets, panicking on the
+        // next edit.
+        prompt.on_key_event(200);
+        prompt.on_key_event(200);
+        assert_eq!(prompt.cursor, 2);
+        assert_eq!(prompt.content.chars().count(), 2);
+
+        prompt.on_key_event(ncurses::KEY_BACKSPACE);
+        assert_eq!(prompt.content.chars().count(), 1);
+    }
+}